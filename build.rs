@@ -0,0 +1,21 @@
+use std::env;
+use std::path::Path;
+
+use syntect::dumps::dump_to_file;
+use syntect::highlighting::ThemeSet;
+use syntect::parsing::SyntaxSet;
+
+/// Pre-parse syntect's bundled syntaxes/themes once at build time and dump
+/// them to `OUT_DIR`, so `bat::assets::HighlightingAssets` can pull them in
+/// via `include_bytes!` instead of re-parsing them on every invocation.
+fn main() {
+    let out_dir = env::var("OUT_DIR").expect("OUT_DIR not set by cargo");
+
+    let syntax_set = SyntaxSet::load_defaults_newlines();
+    dump_to_file(&syntax_set, Path::new(&out_dir).join("syntaxes.bin"))
+        .expect("failed to dump bundled syntax set");
+
+    let theme_set = ThemeSet::load_defaults();
+    dump_to_file(&theme_set, Path::new(&out_dir).join("themes.bin"))
+        .expect("failed to dump bundled theme set");
+}
@@ -0,0 +1,254 @@
+use std::fs::{self, File};
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+
+use syntect::dumps::{dump_to_file, from_binary, from_reader};
+use syntect::highlighting::ThemeSet;
+use syntect::parsing::{SyntaxDefinition, SyntaxSet};
+
+use crate::bat::dirs::{config_dir, syntaxes_dir, themes_dir};
+
+const SYNTAXES_CACHE_FILE: &str = "syntaxes.bin";
+const THEMES_CACHE_FILE: &str = "themes.bin";
+
+/// The syntax and theme definitions delta highlights diffs with. Bundled
+/// with the binary and, once a user config directory exists, merged with
+/// whatever the user has dropped in there.
+pub struct HighlightingAssets {
+    pub syntax_set: SyntaxSet,
+    pub theme_set: ThemeSet,
+}
+
+impl HighlightingAssets {
+    /// Load assets from the on-disk cache when it exists and is at least as
+    /// new as the user's theme/syntax sources, otherwise build them from
+    /// the bundled dumps plus whatever is in the user config directory.
+    pub fn new() -> Self {
+        match load_cached_syntax_set().zip(load_cached_theme_set()) {
+            Some((syntax_set, theme_set)) => Self {
+                syntax_set,
+                theme_set,
+            },
+            None => Self::build(),
+        }
+    }
+
+    fn build() -> Self {
+        let mut theme_set: ThemeSet =
+            from_binary(include_bytes!(concat!(env!("OUT_DIR"), "/themes.bin")));
+        load_user_themes(&mut theme_set);
+
+        let bundled_syntax_set: SyntaxSet =
+            from_binary(include_bytes!(concat!(env!("OUT_DIR"), "/syntaxes.bin")));
+        let syntax_set = load_user_syntaxes(bundled_syntax_set);
+
+        Self {
+            syntax_set,
+            theme_set,
+        }
+    }
+}
+
+/// `delta cache --build`: serialize the merged syntax and theme sets to the
+/// config directory so subsequent launches skip re-parsing user syntaxes
+/// and themes.
+pub fn build_cache() -> std::io::Result<()> {
+    let dir = config_dir().ok_or_else(|| {
+        std::io::Error::new(std::io::ErrorKind::NotFound, "could not determine config directory")
+    })?;
+    fs::create_dir_all(&dir)?;
+    let assets = HighlightingAssets::build();
+    dump_to_file(&assets.syntax_set, cache_path(&dir, SYNTAXES_CACHE_FILE))
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
+    dump_to_file(&assets.theme_set, cache_path(&dir, THEMES_CACHE_FILE))
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
+    Ok(())
+}
+
+/// `delta cache --clear`: remove any cached dumps, falling back to parsing
+/// from source on the next launch.
+pub fn clear_cache() -> std::io::Result<()> {
+    if let Some(dir) = config_dir() {
+        for file in &[SYNTAXES_CACHE_FILE, THEMES_CACHE_FILE] {
+            let path = cache_path(&dir, file);
+            if path.exists() {
+                fs::remove_file(path)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn cache_path(dir: &Path, file: &str) -> PathBuf {
+    dir.join(file)
+}
+
+fn load_cached_syntax_set() -> Option<SyntaxSet> {
+    let dir = config_dir()?;
+    let path = cache_path(&dir, SYNTAXES_CACHE_FILE);
+    if !cache_is_fresh(&path, syntaxes_dir()) {
+        return None;
+    }
+    let reader = BufReader::new(File::open(&path).ok()?);
+    from_reader(reader).ok()
+}
+
+fn load_cached_theme_set() -> Option<ThemeSet> {
+    let dir = config_dir()?;
+    let path = cache_path(&dir, THEMES_CACHE_FILE);
+    if !cache_is_fresh(&path, themes_dir()) {
+        return None;
+    }
+    let reader = BufReader::new(File::open(&path).ok()?);
+    from_reader(reader).ok()
+}
+
+/// `true` if `cache_path` exists and is at least as new as every file in
+/// `source_dir` (or `source_dir` is absent/empty).
+fn cache_is_fresh(cache_path: &Path, source_dir: Option<PathBuf>) -> bool {
+    let cache_modified = match fs::metadata(cache_path).and_then(|m| m.modified()) {
+        Ok(modified) => modified,
+        Err(_) => return false,
+    };
+    let source_dir = match source_dir {
+        Some(dir) if dir.is_dir() => dir,
+        _ => return true,
+    };
+    fs::read_dir(source_dir)
+        .into_iter()
+        .flatten()
+        .filter_map(Result::ok)
+        .filter_map(|entry| entry.metadata().ok()?.modified().ok())
+        .all(|modified| modified <= cache_modified)
+}
+
+/// Scan the user's `themes/` config subdirectory for `.tmTheme` files and
+/// fold each into `theme_set`, so `--theme`, `list_themes`, and
+/// `list_theme_names` see them alongside the bundled themes.
+fn load_user_themes(theme_set: &mut ThemeSet) {
+    let dir = match themes_dir() {
+        Some(dir) if dir.is_dir() => dir,
+        _ => return,
+    };
+    for entry in walk_files(&dir, "tmTheme") {
+        if let Ok(theme) = ThemeSet::get_theme(&entry) {
+            if let Some(name) = entry.file_stem().and_then(|stem| stem.to_str()) {
+                theme_set.themes.insert(name.to_string(), theme);
+            }
+        }
+    }
+}
+
+/// Scan the user's `syntaxes/` config subdirectory for `.sublime-syntax`
+/// files and merge them into a new `SyntaxSet` built from the bundled one.
+fn load_user_syntaxes(bundled: SyntaxSet) -> SyntaxSet {
+    let dir = match syntaxes_dir() {
+        Some(dir) if dir.is_dir() => dir,
+        _ => return bundled,
+    };
+
+    let mut builder = bundled.into_builder();
+    for entry in walk_files(&dir, "sublime-syntax") {
+        if let Ok(definition) = load_syntax_definition(&entry) {
+            builder.add(definition);
+        }
+    }
+    builder.build()
+}
+
+fn load_syntax_definition(path: &Path) -> Result<SyntaxDefinition, syntect::LoadingError> {
+    let source = fs::read_to_string(path)?;
+    Ok(SyntaxDefinition::load_from_str(
+        &source,
+        true,
+        path.file_stem().and_then(|s| s.to_str()),
+    )?)
+}
+
+fn walk_files(dir: &Path, extension: &str) -> Vec<PathBuf> {
+    fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().and_then(|ext| ext.to_str()) == Some(extension))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::time::{Duration, SystemTime};
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("delta-assets-test-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_cache_is_fresh_with_no_source_dir() {
+        let dir = temp_dir("fresh-no-source");
+        let cache = dir.join("cache.bin");
+        fs::write(&cache, b"stub").unwrap();
+        assert!(cache_is_fresh(&cache, None));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_cache_is_fresh_missing_cache_file() {
+        let dir = temp_dir("fresh-missing-cache");
+        assert!(!cache_is_fresh(&dir.join("does-not-exist.bin"), None));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_cache_is_stale_when_source_file_is_newer() {
+        let dir = temp_dir("fresh-stale");
+        let source_dir = dir.join("themes");
+        fs::create_dir_all(&source_dir).unwrap();
+        let cache = dir.join("cache.bin");
+        fs::write(&cache, b"stub").unwrap();
+
+        let now = SystemTime::now();
+        let cache_file = File::open(&cache).unwrap();
+        cache_file
+            .set_modified(now - Duration::from_secs(60))
+            .unwrap();
+
+        let newer_source = source_dir.join("custom.tmTheme");
+        fs::write(&newer_source, b"stub").unwrap();
+
+        assert!(!cache_is_fresh(&cache, Some(source_dir)));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_walk_files_filters_by_extension() {
+        let dir = temp_dir("walk-files");
+        fs::write(dir.join("a.tmTheme"), b"stub").unwrap();
+        fs::write(dir.join("b.txt"), b"stub").unwrap();
+
+        let found = walk_files(&dir, "tmTheme");
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].file_name().unwrap(), "a.tmTheme");
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
+
+pub fn list_languages() -> std::io::Result<()> {
+    let assets = HighlightingAssets::new();
+    let mut langs: Vec<&str> = assets
+        .syntax_set
+        .syntaxes()
+        .iter()
+        .map(|syntax| syntax.name.as_str())
+        .collect();
+    langs.sort_unstable();
+    for lang in langs {
+        println!("{}", lang);
+    }
+    Ok(())
+}
@@ -0,0 +1,27 @@
+use std::path::PathBuf;
+
+use directories::ProjectDirs;
+use lazy_static::lazy_static;
+
+lazy_static! {
+    static ref PROJECT_DIRS: Option<ProjectDirs> =
+        ProjectDirs::from("", "", "delta");
+}
+
+/// The directory delta looks in for user-supplied themes and syntaxes,
+/// honoring `DELTA_CONFIG_DIR` and falling back to the platform's standard
+/// config location (XDG on Linux, the app-data dir elsewhere).
+pub fn config_dir() -> Option<PathBuf> {
+    if let Ok(dir) = std::env::var("DELTA_CONFIG_DIR") {
+        return Some(PathBuf::from(dir));
+    }
+    PROJECT_DIRS.as_ref().map(|dirs| dirs.config_dir().to_path_buf())
+}
+
+pub fn themes_dir() -> Option<PathBuf> {
+    config_dir().map(|dir| dir.join("themes"))
+}
+
+pub fn syntaxes_dir() -> Option<PathBuf> {
+    config_dir().map(|dir| dir.join("syntaxes"))
+}
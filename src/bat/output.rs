@@ -0,0 +1,53 @@
+use std::io::{self, Write};
+use std::process::{Child, Command, Stdio};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PagingMode {
+    Always,
+    QuitIfOneScreen,
+    Never,
+}
+
+pub enum OutputType {
+    Pager(Child),
+    Stdout(io::Stdout),
+}
+
+impl OutputType {
+    pub fn from_mode(mode: PagingMode, pager: Option<&str>) -> io::Result<Self> {
+        use self::PagingMode::*;
+        Ok(match mode {
+            Always | QuitIfOneScreen => {
+                let pager_command = pager.unwrap_or("less");
+                let mut args = vec!["--RAW-CONTROL-CHARS"];
+                if mode == QuitIfOneScreen {
+                    args.push("--quit-if-one-screen");
+                }
+                match Command::new(pager_command)
+                    .args(&args)
+                    .stdin(Stdio::piped())
+                    .spawn()
+                {
+                    Ok(child) => OutputType::Pager(child),
+                    Err(_) => OutputType::Stdout(io::stdout()),
+                }
+            }
+            Never => OutputType::Stdout(io::stdout()),
+        })
+    }
+
+    pub fn handle(&mut self) -> io::Result<&mut dyn Write> {
+        Ok(match *self {
+            OutputType::Pager(ref mut child) => child.stdin.as_mut().unwrap(),
+            OutputType::Stdout(ref mut stdout) => stdout,
+        })
+    }
+}
+
+impl Drop for OutputType {
+    fn drop(&mut self) {
+        if let OutputType::Pager(ref mut child) = *self {
+            let _ = child.wait();
+        }
+    }
+}
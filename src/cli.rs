@@ -0,0 +1,150 @@
+use structopt::StructOpt;
+
+use crate::bat::assets::HighlightingAssets;
+use crate::bat::output::PagingMode;
+use crate::config::Config;
+use crate::style::StyleModifier;
+
+#[derive(StructOpt, Clone, Debug)]
+#[structopt(name = "delta", about = "A syntax-highlighting pager for git diff output")]
+pub struct Opt {
+    #[structopt(long = "plus-color")]
+    pub plus_color: Option<String>,
+
+    #[structopt(long = "plus-emph-color")]
+    pub plus_emph_color: Option<String>,
+
+    #[structopt(long = "minus-color")]
+    pub minus_color: Option<String>,
+
+    #[structopt(long = "minus-emph-color")]
+    pub minus_emph_color: Option<String>,
+
+    #[structopt(long = "theme")]
+    pub theme: Option<String>,
+
+    #[structopt(long = "list-languages")]
+    pub list_languages: bool,
+
+    #[structopt(long = "list-themes")]
+    pub list_themes: bool,
+
+    #[structopt(long = "list-theme-names")]
+    pub list_theme_names: bool,
+
+    #[structopt(long = "light")]
+    pub light: bool,
+
+    #[structopt(long = "dark")]
+    pub dark: bool,
+
+    #[structopt(long = "show-background-colors")]
+    pub show_background_colors: bool,
+
+    /// Display line numbers next to each line, derived from the hunk header.
+    #[structopt(long = "line-numbers")]
+    pub line_numbers: bool,
+
+    #[structopt(long = "paging")]
+    pub paging_mode: Option<String>,
+
+    /// Diff the index against HEAD instead of the working tree against the
+    /// index. Only meaningful when path arguments are given.
+    #[structopt(long = "staged", alias = "cached")]
+    pub staged: bool,
+
+    /// A `<commit>..<commit>` range to diff directly, instead of the
+    /// working tree/index. Only meaningful when path arguments are given.
+    #[structopt(long = "diff")]
+    pub commit_range: Option<String>,
+
+    /// Files to diff via git2, in place of reading a diff from stdin.
+    #[structopt(parse(from_os_str))]
+    pub paths: Vec<std::path::PathBuf>,
+
+    #[structopt(subcommand)]
+    pub subcommand: Option<Subcommand>,
+}
+
+#[derive(StructOpt, Clone, Debug)]
+pub enum Subcommand {
+    /// Manage the precompiled syntax/theme cache in the config directory.
+    Cache {
+        /// Build the cache from the bundled and user-supplied assets.
+        #[structopt(long = "build")]
+        build: bool,
+
+        /// Remove any existing cache, falling back to parsing from source.
+        #[structopt(long = "clear")]
+        clear: bool,
+    },
+}
+
+pub fn process_command_line_arguments(assets: &HighlightingAssets, opt: &Opt) -> Config {
+    let theme_name = opt
+        .theme
+        .clone()
+        .unwrap_or_else(|| "Monokai Extended".to_string());
+    let is_light = assets
+        .theme_set
+        .themes
+        .get(&theme_name)
+        .map(|_| crate::style::is_light_theme(&theme_name))
+        .unwrap_or(false);
+
+    let paging_mode = match opt.paging_mode.as_deref() {
+        Some("always") => PagingMode::Always,
+        Some("never") => PagingMode::Never,
+        _ => PagingMode::QuitIfOneScreen,
+    };
+
+    Config {
+        theme_name,
+        light_theme: is_light,
+        minus_style_modifier: StyleModifier {
+            background: Some(syntect::highlighting::Color {
+                r: 0x3f,
+                g: 0x00,
+                b: 0x01,
+                a: 0xff,
+            }),
+            ..StyleModifier::default()
+        },
+        minus_emph_style_modifier: StyleModifier {
+            background: Some(syntect::highlighting::Color {
+                r: 0x90,
+                g: 0x10,
+                b: 0x10,
+                a: 0xff,
+            }),
+            ..StyleModifier::default()
+        },
+        plus_style_modifier: StyleModifier {
+            background: Some(syntect::highlighting::Color {
+                r: 0x00,
+                g: 0x28,
+                b: 0x00,
+                a: 0xff,
+            }),
+            ..StyleModifier::default()
+        },
+        plus_emph_style_modifier: StyleModifier {
+            background: Some(syntect::highlighting::Color {
+                r: 0x10,
+                g: 0x60,
+                b: 0x10,
+                a: 0xff,
+            }),
+            ..StyleModifier::default()
+        },
+        paging_mode,
+        true_color: is_truecolor_terminal(),
+        line_numbers: opt.line_numbers,
+    }
+}
+
+fn is_truecolor_terminal() -> bool {
+    std::env::var("COLORTERM")
+        .map(|colorterm| colorterm == "truecolor" || colorterm == "24bit")
+        .unwrap_or(false)
+}
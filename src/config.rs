@@ -0,0 +1,14 @@
+use crate::bat::output::PagingMode;
+use crate::style::StyleModifier;
+
+pub struct Config {
+    pub theme_name: String,
+    pub light_theme: bool,
+    pub minus_style_modifier: StyleModifier,
+    pub minus_emph_style_modifier: StyleModifier,
+    pub plus_style_modifier: StyleModifier,
+    pub plus_emph_style_modifier: StyleModifier,
+    pub paging_mode: PagingMode,
+    pub true_color: bool,
+    pub line_numbers: bool,
+}
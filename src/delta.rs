@@ -0,0 +1,147 @@
+use std::io::{self, Write};
+
+use syntect::easy::HighlightLines;
+use syntect::highlighting::Style;
+
+use crate::bat::assets::HighlightingAssets;
+use crate::config::Config;
+use crate::draw::write_horizontal_rule;
+use crate::env::terminal_width;
+use crate::paint::paint_text;
+use crate::parse::{parse_hunk_header, HunkHeader, LineNumbers};
+use crate::style::{self, StyleModifier};
+
+#[derive(PartialEq)]
+enum State {
+    Unknown,
+    FileMeta,
+    HunkHeader,
+    HunkZero,
+    HunkMinus,
+    HunkPlus,
+}
+
+/// Read a `git diff`-style stream of lines and write a syntax-highlighted,
+/// delta-styled rendering of it to `writer`.
+pub fn delta(
+    lines: impl Iterator<Item = String>,
+    config: &Config,
+    assets: &HighlightingAssets,
+    writer: &mut dyn Write,
+) -> io::Result<()> {
+    let mut state = State::Unknown;
+    let theme = &assets.theme_set.themes[&config.theme_name];
+    let syntax = assets
+        .syntax_set
+        .find_syntax_by_extension("rs")
+        .unwrap_or_else(|| assets.syntax_set.find_syntax_plain_text());
+    let mut highlighter = HighlightLines::new(syntax, theme);
+
+    let mut current_hunk_header: Option<HunkHeader> = None;
+    let mut line_numbers = LineNumbers::default();
+    let mut gutter_width = 0;
+
+    for raw_line in lines {
+        if raw_line.starts_with("diff --git") {
+            state = State::FileMeta;
+            writeln!(writer, "{}", raw_line)?;
+            continue;
+        }
+        if raw_line.starts_with("--- ") || raw_line.starts_with("+++ ") {
+            writeln!(writer, "{}", raw_line)?;
+            if raw_line.starts_with("+++ ") {
+                write_horizontal_rule(writer, terminal_width())?;
+            }
+            continue;
+        }
+        if raw_line.starts_with("@@") {
+            state = State::HunkHeader;
+            let header = parse_hunk_header(&raw_line)?;
+            line_numbers = LineNumbers::from_hunk_header(&header);
+            gutter_width = if config.line_numbers {
+                header.max_line_number().to_string().len()
+            } else {
+                0
+            };
+            current_hunk_header = Some(header);
+            writeln!(writer, "{}", raw_line)?;
+            continue;
+        }
+
+        let (origin, content) = match raw_line.chars().next() {
+            Some('-') => (Some('-'), &raw_line[1..]),
+            Some('+') => (Some('+'), &raw_line[1..]),
+            Some(' ') => (Some(' '), &raw_line[1..]),
+            _ => (None, raw_line.as_str()),
+        };
+
+        if current_hunk_header.is_none() || origin.is_none() {
+            writeln!(writer, "{}", raw_line)?;
+            continue;
+        }
+        let origin = origin.unwrap();
+        state = match origin {
+            '-' => State::HunkMinus,
+            '+' => State::HunkPlus,
+            _ => State::HunkZero,
+        };
+
+        let style_modifier = match state {
+            State::HunkMinus => Some(&config.minus_style_modifier),
+            State::HunkPlus => Some(&config.plus_style_modifier),
+            _ => None,
+        };
+
+        let mut painted = String::new();
+        if config.line_numbers {
+            let (old_no, new_no) = line_numbers.advance(origin);
+            painted.push_str(&format_gutter(old_no, new_no, gutter_width, config.true_color));
+        }
+
+        for (base_style, text) in highlighter.highlight(content, &assets.syntax_set) {
+            let style = apply_modifier(base_style, style_modifier);
+            paint_text(text, style, &mut painted, config.true_color);
+        }
+        writeln!(writer, "{}{}", origin, painted)?;
+    }
+    let _ = state;
+    Ok(())
+}
+
+fn apply_modifier(base: Style, modifier: Option<&StyleModifier>) -> Style {
+    let mut style = base;
+    if let Some(modifier) = modifier {
+        if let Some(background) = modifier.background {
+            style.background = background;
+        }
+        if let Some(foreground) = modifier.foreground {
+            style.foreground = foreground;
+        }
+        if let Some(font_style) = modifier.font_style {
+            style.font_style = font_style;
+        }
+    }
+    style
+}
+
+/// Render the `old new │ ` line-number gutter for one diff line, padded to
+/// `width` per side and dimmed so it doesn't compete with syntax colors.
+fn format_gutter(old_no: Option<usize>, new_no: Option<usize>, width: usize, true_color: bool) -> String {
+    let column = |n: Option<usize>| match n {
+        Some(n) => format!("{:>width$}", n, width = width),
+        None => " ".repeat(width),
+    };
+    let gutter_style = Style {
+        foreground: style::GUTTER_DIM,
+        background: style::NO_COLOR,
+        font_style: syntect::highlighting::FontStyle::empty(),
+    };
+    let mut painted = String::new();
+    paint_text(
+        &format!("{} {} │ ", column(old_no), column(new_no)),
+        gutter_style,
+        &mut painted,
+        true_color,
+    );
+    painted
+}
@@ -0,0 +1,7 @@
+use std::io::Write;
+
+/// Print a horizontal rule the width of the terminal, used to separate
+/// file headers from their hunks.
+pub fn write_horizontal_rule(writer: &mut dyn Write, width: usize) -> std::io::Result<()> {
+    writeln!(writer, "{}", "─".repeat(width))
+}
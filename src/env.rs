@@ -0,0 +1,5 @@
+/// Terminal width, falling back to bat's default of 80 columns when it
+/// can't be determined (e.g. output is piped to a file).
+pub fn terminal_width() -> usize {
+    term_size::dimensions().map(|(w, _)| w).unwrap_or(80)
+}
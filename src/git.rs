@@ -0,0 +1,105 @@
+use std::path::PathBuf;
+
+use git2::{DiffFormat, DiffOptions, Repository, Tree};
+
+/// Compute a unified diff for `paths` directly via `git2`, so `delta` can be
+/// pointed at files instead of requiring a caller to pipe `git diff` in.
+///
+/// With no `commit_range`, diffs the working tree against the index
+/// (or, with `staged`, the index against `HEAD`). With a `commit_range` of
+/// the form `<commit>..<commit>`, diffs the two trees directly.
+pub fn diff_lines(
+    paths: &[PathBuf],
+    staged: bool,
+    commit_range: Option<&str>,
+) -> Result<Vec<String>, git2::Error> {
+    let repo = Repository::discover(".")?;
+
+    let mut diff_opts = DiffOptions::new();
+    for path in paths {
+        diff_opts.pathspec(path);
+    }
+
+    let diff = match commit_range {
+        Some(range) => {
+            let (old_tree, new_tree) = resolve_range(&repo, range)?;
+            repo.diff_tree_to_tree(Some(&old_tree), Some(&new_tree), Some(&mut diff_opts))?
+        }
+        None if staged => {
+            let head_tree = repo.head()?.peel_to_tree()?;
+            repo.diff_tree_to_index(Some(&head_tree), None, Some(&mut diff_opts))?
+        }
+        None => repo.diff_index_to_workdir(None, Some(&mut diff_opts))?,
+    };
+
+    Ok(collect_patch_lines(&diff)?)
+}
+
+/// Split a `<commit>..<commit>` range selector into its two revisions, each
+/// defaulting to `HEAD` when its side of the range is omitted.
+fn split_range(range: &str) -> (&str, &str) {
+    let mut sides = range.splitn(2, "..");
+    let old = sides.next().unwrap_or("HEAD");
+    let new = sides.next().unwrap_or("HEAD");
+    (
+        if old.is_empty() { "HEAD" } else { old },
+        if new.is_empty() { "HEAD" } else { new },
+    )
+}
+
+/// Resolve a `<commit>..<commit>` range selector to the two trees it names.
+fn resolve_range<'repo>(
+    repo: &'repo Repository,
+    range: &str,
+) -> Result<(Tree<'repo>, Tree<'repo>), git2::Error> {
+    let (old, new) = split_range(range);
+    let old_tree = repo.revparse_single(old)?.peel_to_tree()?;
+    let new_tree = repo.revparse_single(new)?.peel_to_tree()?;
+    Ok((old_tree, new_tree))
+}
+
+/// Render a `git2::Diff` as the same line-oriented unified-diff text that
+/// `git diff` would print, ready to feed into the existing `delta(...)`
+/// pipeline.
+fn collect_patch_lines(diff: &git2::Diff<'_>) -> Result<Vec<String>, git2::Error> {
+    let mut lines: Vec<String> = Vec::new();
+    let mut current = String::new();
+    diff.print(DiffFormat::Patch, |_delta, _hunk, line| {
+        match line.origin() {
+            '+' | '-' | ' ' => current.push(line.origin()),
+            _ => (),
+        }
+        current.push_str(&String::from_utf8_lossy(line.content()));
+        for piece in current.split_terminator('\n') {
+            lines.push(piece.to_string());
+        }
+        current.clear();
+        true
+    })?;
+    Ok(lines)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_range_both_sides() {
+        assert_eq!(split_range("abc123..def456"), ("abc123", "def456"));
+    }
+
+    #[test]
+    fn test_split_range_missing_old_side_defaults_to_head() {
+        assert_eq!(split_range("..def456"), ("HEAD", "def456"));
+    }
+
+    #[test]
+    fn test_split_range_missing_new_side_defaults_to_head() {
+        assert_eq!(split_range("abc123.."), ("abc123", "HEAD"));
+    }
+
+    #[test]
+    fn test_split_range_no_separator_diffs_against_head() {
+        assert_eq!(split_range("abc123"), ("abc123", "HEAD"));
+    }
+}
@@ -1,14 +1,13 @@
 #[macro_use]
 extern crate error_chain;
 
-mod align;
 mod bat;
 mod cli;
 mod config;
 mod delta;
 mod draw;
-mod edits;
 mod env;
+mod git;
 mod paint;
 mod parse;
 mod style;
@@ -21,8 +20,9 @@ use atty;
 use structopt::StructOpt;
 use syntect::highlighting::{Color, FontStyle, Style};
 
-use crate::bat::assets::{list_languages, HighlightingAssets};
+use crate::bat::assets::{build_cache, clear_cache, list_languages, HighlightingAssets};
 use crate::bat::output::{OutputType, PagingMode};
+use crate::cli::Subcommand;
 use crate::delta::delta;
 
 mod errors {
@@ -38,6 +38,16 @@ mod errors {
 fn main() -> std::io::Result<()> {
     let opt = cli::Opt::from_args();
 
+    if let Some(Subcommand::Cache { build, clear }) = &opt.subcommand {
+        if *clear {
+            clear_cache()?;
+        }
+        if *build {
+            build_cache()?;
+        }
+        process::exit(0);
+    }
+
     let assets = HighlightingAssets::new();
 
     if opt.list_languages {
@@ -61,12 +71,24 @@ fn main() -> std::io::Result<()> {
     let mut output_type = OutputType::from_mode(config.paging_mode, None).unwrap();
     let mut writer = output_type.handle().unwrap();
 
-    if let Err(error) = delta(
-        io::stdin().lock().lines().map(|l| l.unwrap()),
-        &config,
-        &assets,
-        &mut writer,
-    ) {
+    let delta_result = if opt.paths.is_empty() {
+        delta(
+            io::stdin().lock().lines().map(|l| l.unwrap()),
+            &config,
+            &assets,
+            &mut writer,
+        )
+    } else {
+        match git::diff_lines(&opt.paths, opt.staged, opt.commit_range.as_deref()) {
+            Ok(lines) => delta(lines.into_iter(), &config, &assets, &mut writer),
+            Err(error) => {
+                eprintln!("{}", error);
+                process::exit(1);
+            }
+        }
+    };
+
+    if let Err(error) = delta_result {
         match error.kind() {
             ErrorKind::BrokenPipe => process::exit(0),
             _ => eprintln!("{}", error),
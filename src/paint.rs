@@ -0,0 +1,36 @@
+use syntect::highlighting::Style;
+
+/// Write `text` to `output` wrapped in ANSI escape codes for `style`.
+///
+/// When `true_color` is false, colors are downsampled to the nearest ANSI
+/// 256-color palette entry, matching bat's behavior on terminals that don't
+/// advertise 24-bit color support.
+pub fn paint_text(text: &str, style: Style, output: &mut String, true_color: bool) {
+    let ansi_style = ansi_term::Style {
+        foreground: Some(color_to_ansi(style.foreground, true_color)),
+        background: if style.background.a == 0 {
+            None
+        } else {
+            Some(color_to_ansi(style.background, true_color))
+        },
+        is_bold: style
+            .font_style
+            .contains(syntect::highlighting::FontStyle::BOLD),
+        is_italic: style
+            .font_style
+            .contains(syntect::highlighting::FontStyle::ITALIC),
+        is_underline: style
+            .font_style
+            .contains(syntect::highlighting::FontStyle::UNDERLINE),
+        ..ansi_term::Style::default()
+    };
+    output.push_str(&ansi_style.paint(text).to_string());
+}
+
+fn color_to_ansi(color: syntect::highlighting::Color, true_color: bool) -> ansi_term::Color {
+    if true_color {
+        ansi_term::Color::RGB(color.r, color.g, color.b)
+    } else {
+        ansi_term::Color::Fixed(ansi_colours::ansi256_from_rgb((color.r, color.g, color.b)))
+    }
+}
@@ -0,0 +1,143 @@
+use std::io;
+
+/// The `@@ -old_start,old_count +new_start,new_count @@` header of a diff
+/// hunk, with the two line-number counters it seeds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HunkHeader {
+    pub minus_line: usize,
+    pub minus_count: usize,
+    pub plus_line: usize,
+    pub plus_count: usize,
+}
+
+impl HunkHeader {
+    /// The largest line number that will be printed while rendering this
+    /// hunk, used to size the line-number gutter.
+    pub fn max_line_number(&self) -> usize {
+        let minus_last = self.minus_line + self.minus_count.saturating_sub(1);
+        let plus_last = self.plus_line + self.plus_count.saturating_sub(1);
+        minus_last.max(plus_last)
+    }
+}
+
+pub fn parse_hunk_header(line: &str) -> io::Result<HunkHeader> {
+    // @@ -3,7 +3,8 @@ optional section heading
+    let err = || io::Error::new(io::ErrorKind::InvalidData, format!("invalid hunk header: {}", line));
+    let range_part = line
+        .trim_start_matches("@@ ")
+        .split(" @@")
+        .next()
+        .ok_or_else(err)?;
+    let mut sides = range_part.split_whitespace();
+    let minus = parse_range(sides.next().ok_or_else(err)?, '-').ok_or_else(err)?;
+    let plus = parse_range(sides.next().ok_or_else(err)?, '+').ok_or_else(err)?;
+    Ok(HunkHeader {
+        minus_line: minus.0,
+        minus_count: minus.1,
+        plus_line: plus.0,
+        plus_count: plus.1,
+    })
+}
+
+fn parse_range(s: &str, sign: char) -> Option<(usize, usize)> {
+    let s = s.strip_prefix(sign)?;
+    let mut parts = s.splitn(2, ',');
+    let start: usize = parts.next()?.parse().ok()?;
+    let count: usize = match parts.next() {
+        Some(count) => count.parse().ok()?,
+        None => 1,
+    };
+    Some((start, count))
+}
+
+/// Running original/new-side line counters for the hunk currently being
+/// rendered, used to print the `--line-numbers` gutter.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LineNumbers {
+    pub minus: usize,
+    pub plus: usize,
+}
+
+impl LineNumbers {
+    pub fn from_hunk_header(header: &HunkHeader) -> Self {
+        Self {
+            minus: header.minus_line,
+            plus: header.plus_line,
+        }
+    }
+
+    /// Advance the counters for a context/`-`/`+` line and return the
+    /// (old, new) numbers to print for it; `None` on a side that doesn't
+    /// apply to this line.
+    pub fn advance(&mut self, origin: char) -> (Option<usize>, Option<usize>) {
+        match origin {
+            ' ' => {
+                let result = (Some(self.minus), Some(self.plus));
+                self.minus += 1;
+                self.plus += 1;
+                result
+            }
+            '-' => {
+                let result = (Some(self.minus), None);
+                self.minus += 1;
+                result
+            }
+            '+' => {
+                let result = (None, Some(self.plus));
+                self.plus += 1;
+                result
+            }
+            _ => (None, None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_hunk_header() {
+        let header = parse_hunk_header("@@ -3,7 +3,8 @@ fn foo() {").unwrap();
+        assert_eq!(
+            header,
+            HunkHeader {
+                minus_line: 3,
+                minus_count: 7,
+                plus_line: 3,
+                plus_count: 8,
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_hunk_header_single_line_counts() {
+        let header = parse_hunk_header("@@ -1 +1 @@").unwrap();
+        assert_eq!(
+            header,
+            HunkHeader {
+                minus_line: 1,
+                minus_count: 1,
+                plus_line: 1,
+                plus_count: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn test_line_numbers_advance() {
+        let header = parse_hunk_header("@@ -3,2 +3,3 @@").unwrap();
+        let mut numbers = LineNumbers::from_hunk_header(&header);
+        assert_eq!(numbers.advance(' '), (Some(3), Some(3)));
+        assert_eq!(numbers.advance('-'), (Some(4), None));
+        assert_eq!(numbers.advance('+'), (None, Some(4)));
+        assert_eq!(numbers.advance('+'), (None, Some(5)));
+    }
+
+    #[test]
+    fn test_max_line_number_is_last_printed_line_not_one_past_it() {
+        // Lines 95-99 are printed; the gutter should size for 99, not 100.
+        let header = parse_hunk_header("@@ -95,5 +95,5 @@").unwrap();
+        assert_eq!(header.max_line_number(), 99);
+    }
+}
@@ -0,0 +1,39 @@
+use syntect::highlighting::Color;
+
+pub const NO_COLOR: Color = Color {
+    r: 0,
+    g: 0,
+    b: 0,
+    a: 0,
+};
+
+/// Muted gray used for the `--line-numbers` gutter, so it reads as
+/// secondary to the syntax-highlighted diff content on both light and dark
+/// backgrounds.
+pub const GUTTER_DIM: Color = Color {
+    r: 0x6c,
+    g: 0x6c,
+    b: 0x6c,
+    a: 0xff,
+};
+
+/// A small set of style attributes layered on top of whatever the syntax
+/// highlighter produced for a line. `None` fields mean "leave as-is".
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StyleModifier {
+    pub foreground: Option<Color>,
+    pub background: Option<Color>,
+    pub font_style: Option<syntect::highlighting::FontStyle>,
+}
+
+/// Themes whose names match bat's convention for light variants.
+pub fn is_light_theme(theme_name: &str) -> bool {
+    [
+        "GitHub",
+        "Monokai Extended Light",
+        "ansi-light",
+        "Solarized (light)",
+        "Source Code Pro (light)",
+    ]
+    .contains(&theme_name)
+}